@@ -0,0 +1,145 @@
+//! A small embedded 5x7 bitmap font, used by `MonoFrame::draw_text` and
+//! `ColorFrame::draw_text` to rasterize text at an arbitrary pixel position.
+
+/// Glyph width in pixels.
+pub const GLYPH_WIDTH: usize = 5;
+/// Glyph height in pixels.
+pub const GLYPH_HEIGHT: usize = 7;
+
+/// A single glyph, stored column-major: column `c`'s byte has bit `r` set
+/// if row `r` (0 = top) is lit.
+pub type Glyph = [u8; GLYPH_WIDTH];
+
+/// A reasonable default for the fallback glyph passed to
+/// `MonoFrame::draw_text_with_fallback`/`ColorFrame::draw_text_with_fallback`,
+/// drawn for any character with no entry in the font table (e.g. non-ASCII
+/// code points).
+pub const FALLBACK_GLYPH: Glyph = [0x1f, 0x11, 0x11, 0x11, 0x1f];
+
+/// Covers the full printable ASCII range, `0x20` (space) through `0x7e`
+/// (`~`), one entry per character, searched linearly by `glyph_for`.
+const TABLE: &'static [(char, Glyph)] = &[
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('!', [0x00, 0x00, 0x5f, 0x00, 0x00]),
+    ('"', [0x00, 0x03, 0x00, 0x03, 0x00]),
+    ('#', [0x0a, 0x1f, 0x0a, 0x1f, 0x0a]),
+    ('$', [0x24, 0x2a, 0x7f, 0x2a, 0x12]),
+    ('%', [0x23, 0x13, 0x08, 0x64, 0x62]),
+    ('&', [0x32, 0x4d, 0x4d, 0x32, 0x48]),
+    ('\'', [0x00, 0x02, 0x01, 0x00, 0x00]),
+    ('(', [0x00, 0x1c, 0x22, 0x41, 0x00]),
+    (')', [0x00, 0x41, 0x22, 0x1c, 0x00]),
+    ('*', [0x2a, 0x1c, 0x3e, 0x1c, 0x2a]),
+    ('+', [0x08, 0x08, 0x3e, 0x08, 0x08]),
+    (',', [0x00, 0x40, 0x30, 0x00, 0x00]),
+    ('-', [0x08, 0x08, 0x08, 0x08, 0x08]),
+    ('.', [0x00, 0x00, 0x20, 0x00, 0x00]),
+    ('/', [0x10, 0x08, 0x04, 0x02, 0x01]),
+    ('0', [0x3e, 0x51, 0x49, 0x45, 0x3e]),
+    ('1', [0x00, 0x42, 0x7f, 0x40, 0x00]),
+    ('2', [0x42, 0x61, 0x51, 0x49, 0x46]),
+    ('3', [0x22, 0x41, 0x49, 0x49, 0x36]),
+    ('4', [0x18, 0x14, 0x12, 0x7f, 0x10]),
+    ('5', [0x2f, 0x49, 0x49, 0x49, 0x31]),
+    ('6', [0x3c, 0x4a, 0x49, 0x49, 0x30]),
+    ('7', [0x01, 0x71, 0x09, 0x05, 0x03]),
+    ('8', [0x36, 0x49, 0x49, 0x49, 0x36]),
+    ('9', [0x06, 0x49, 0x49, 0x29, 0x1e]),
+    (':', [0x00, 0x00, 0x12, 0x00, 0x00]),
+    (';', [0x00, 0x40, 0x32, 0x00, 0x00]),
+    ('<', [0x00, 0x04, 0x0a, 0x11, 0x00]),
+    ('=', [0x0a, 0x0a, 0x0a, 0x0a, 0x0a]),
+    ('>', [0x00, 0x11, 0x0a, 0x04, 0x00]),
+    ('?', [0x02, 0x01, 0x51, 0x09, 0x06]),
+    ('@', [0x3e, 0x41, 0x5d, 0x55, 0x0e]),
+    ('A', [0x7c, 0x12, 0x11, 0x12, 0x7c]),
+    ('B', [0x7f, 0x49, 0x49, 0x49, 0x36]),
+    ('C', [0x3e, 0x41, 0x41, 0x41, 0x41]),
+    ('D', [0x7f, 0x41, 0x41, 0x41, 0x3e]),
+    ('E', [0x7f, 0x49, 0x49, 0x49, 0x41]),
+    ('F', [0x7f, 0x09, 0x09, 0x09, 0x01]),
+    ('G', [0x3e, 0x41, 0x49, 0x49, 0x79]),
+    ('H', [0x7f, 0x08, 0x08, 0x08, 0x7f]),
+    ('I', [0x00, 0x41, 0x7f, 0x41, 0x00]),
+    ('J', [0x30, 0x40, 0x40, 0x40, 0x3f]),
+    ('K', [0x7f, 0x08, 0x14, 0x22, 0x41]),
+    ('L', [0x7f, 0x40, 0x40, 0x40, 0x40]),
+    ('M', [0x7f, 0x02, 0x04, 0x02, 0x7f]),
+    ('N', [0x7f, 0x02, 0x04, 0x08, 0x7f]),
+    ('O', [0x3e, 0x41, 0x41, 0x41, 0x3e]),
+    ('P', [0x7f, 0x09, 0x09, 0x09, 0x06]),
+    ('Q', [0x3e, 0x41, 0x51, 0x21, 0x5e]),
+    ('R', [0x7f, 0x09, 0x19, 0x29, 0x46]),
+    ('S', [0x46, 0x49, 0x49, 0x49, 0x31]),
+    ('T', [0x01, 0x01, 0x7f, 0x01, 0x01]),
+    ('U', [0x3f, 0x40, 0x40, 0x40, 0x3f]),
+    ('V', [0x1f, 0x20, 0x40, 0x20, 0x1f]),
+    ('W', [0x7f, 0x20, 0x18, 0x20, 0x7f]),
+    ('X', [0x63, 0x14, 0x08, 0x14, 0x63]),
+    ('Y', [0x03, 0x04, 0x78, 0x04, 0x03]),
+    ('Z', [0x61, 0x51, 0x49, 0x45, 0x43]),
+    ('[', [0x00, 0x7f, 0x41, 0x00, 0x00]),
+    ('\\', [0x01, 0x02, 0x04, 0x08, 0x10]),
+    (']', [0x00, 0x00, 0x41, 0x7f, 0x00]),
+    ('^', [0x00, 0x02, 0x01, 0x02, 0x00]),
+    ('_', [0x40, 0x40, 0x40, 0x40, 0x40]),
+    ('`', [0x00, 0x01, 0x02, 0x00, 0x00]),
+    ('a', [0x10, 0x2a, 0x2a, 0x2a, 0x3c]),
+    ('b', [0x3f, 0x24, 0x24, 0x24, 0x18]),
+    ('c', [0x1c, 0x22, 0x22, 0x22, 0x00]),
+    ('d', [0x18, 0x24, 0x24, 0x24, 0x3f]),
+    ('e', [0x1c, 0x2a, 0x2a, 0x2a, 0x0c]),
+    ('f', [0x04, 0x3e, 0x05, 0x05, 0x00]),
+    ('g', [0x0c, 0x52, 0x52, 0x52, 0x3e]),
+    ('h', [0x3f, 0x04, 0x04, 0x04, 0x38]),
+    ('i', [0x00, 0x24, 0x3d, 0x20, 0x00]),
+    ('j', [0x20, 0x44, 0x44, 0x05, 0x38]),
+    ('k', [0x3f, 0x08, 0x08, 0x14, 0x20]),
+    ('l', [0x00, 0x21, 0x3f, 0x20, 0x00]),
+    ('m', [0x3e, 0x02, 0x1c, 0x02, 0x3c]),
+    ('n', [0x3e, 0x02, 0x02, 0x02, 0x3c]),
+    ('o', [0x1c, 0x22, 0x22, 0x22, 0x1c]),
+    ('p', [0x7e, 0x12, 0x12, 0x12, 0x0c]),
+    ('q', [0x0c, 0x12, 0x12, 0x12, 0x7e]),
+    ('r', [0x3e, 0x04, 0x02, 0x02, 0x00]),
+    ('s', [0x24, 0x2a, 0x2a, 0x2a, 0x12]),
+    ('t', [0x02, 0x1f, 0x22, 0x22, 0x02]),
+    ('u', [0x1e, 0x20, 0x20, 0x20, 0x3e]),
+    ('v', [0x0e, 0x10, 0x20, 0x10, 0x0e]),
+    ('w', [0x1e, 0x20, 0x18, 0x20, 0x1e]),
+    ('x', [0x22, 0x14, 0x08, 0x14, 0x22]),
+    ('y', [0x0e, 0x50, 0x50, 0x50, 0x3e]),
+    ('z', [0x22, 0x32, 0x2a, 0x26, 0x22]),
+    ('{', [0x04, 0x3e, 0x41, 0x41, 0x00]),
+    ('|', [0x00, 0x00, 0x7f, 0x00, 0x00]),
+    ('}', [0x00, 0x41, 0x41, 0x3e, 0x04]),
+    ('~', [0x08, 0x04, 0x08, 0x10, 0x08]),
+];
+
+/// Looks up the glyph for `ch`. Returns `None` for characters with no entry
+/// in the table (anything outside printable ASCII), leaving the choice of
+/// fallback glyph to the caller.
+pub fn glyph_for(ch: char) -> Option<Glyph> {
+    TABLE.iter()
+        .find(|&&(c, _)| c == ch)
+        .map(|&(_, glyph)| glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_for_hits_return_the_table_entry() {
+        assert_eq!(glyph_for('A'), Some([0x7c, 0x12, 0x11, 0x12, 0x7c]));
+        assert_eq!(glyph_for(' '), Some([0x00, 0x00, 0x00, 0x00, 0x00]));
+        assert_eq!(glyph_for('~'), Some([0x08, 0x04, 0x08, 0x10, 0x08]));
+    }
+
+    #[test]
+    fn glyph_for_misses_return_none() {
+        assert_eq!(glyph_for('\u{1}'), None);
+        assert_eq!(glyph_for('\u{7f}'), None);
+        assert_eq!(glyph_for('\u{e9}'), None);
+    }
+}
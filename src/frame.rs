@@ -0,0 +1,476 @@
+//! Owned framebuffer types for composing backgrounds before sending them to
+//! the lcd, instead of hand-assembling a raw byte slice of the right length.
+
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{COLOR_BYTES_PER_PIXEL, COLOR_HEIGHT, COLOR_WIDTH, MONO_HEIGHT, MONO_WIDTH};
+
+/// Horizontal gap, in pixels, drawn between glyphs by `draw_text`.
+const GLYPH_SPACING: usize = 1;
+
+/// An RGBA color, used by `ColorFrame` so callers don't have to remember the
+/// panel's BGRA channel order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        Rgba { r: r, g: g, b: b, a: a }
+    }
+}
+
+/// An owned 160x43 one-byte-per-pixel buffer matching the monochrome lcd's
+/// background format.
+///
+/// ### Notes:
+/// The SDK will turn on the pixel on the screen if the value assigned to
+/// that byte is >= 128, it will remain off if the value is < 128.
+///
+#[derive(Debug, Clone)]
+pub struct MonoFrame {
+    pixels: Box<[u8]>,
+}
+
+impl MonoFrame {
+    /// Creates a new frame, cleared to `0` (off).
+    pub fn new() -> MonoFrame {
+        MonoFrame {
+            pixels: vec![0u8; MONO_WIDTH * MONO_HEIGHT].into_boxed_slice(),
+        }
+    }
+
+    /// Returns the frame as a `160x43` byte slice, ready to hand to
+    /// `Lcd::set_mono_background`, or use `Lcd::set_mono_background_frame`
+    /// to pass the frame directly.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Sets every pixel to `value`.
+    pub fn clear(&mut self, value: u8) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = value;
+        }
+    }
+
+    /// Sets a single pixel.
+    ///
+    /// ### Panics
+    /// Will panic if `x >= 160` or `y >= 43`.
+    ///
+    pub fn set_pixel(&mut self, x: usize, y: usize, value: u8) {
+        assert!(x < MONO_WIDTH && y < MONO_HEIGHT);
+        self.pixels[y * MONO_WIDTH + x] = value;
+    }
+
+    /// Reads back a single pixel.
+    ///
+    /// ### Panics
+    /// Will panic if `x >= 160` or `y >= 43`.
+    ///
+    pub fn get_pixel(&self, x: usize, y: usize) -> u8 {
+        assert!(x < MONO_WIDTH && y < MONO_HEIGHT);
+        self.pixels[y * MONO_WIDTH + x]
+    }
+
+    /// Fills the rectangle `(x, y)..(x + w, y + h)` with `value`, clipped to
+    /// the frame bounds.
+    pub fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize, value: u8) {
+        for py in y..y.saturating_add(h).min(MONO_HEIGHT) {
+            for px in x..x.saturating_add(w).min(MONO_WIDTH) {
+                self.set_pixel(px, py, value);
+            }
+        }
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)` using Bresenham's
+    /// algorithm, clipped to the frame bounds.
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, value: u8) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+
+        loop {
+            if x >= 0 && y >= 0 && (x as usize) < MONO_WIDTH && (y as usize) < MONO_HEIGHT {
+                self.set_pixel(x as usize, y as usize, value);
+            }
+
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Rasterizes `text` onto this frame with its top-left glyph corner at
+    /// `(x, y)`, using the crate's built-in 5x7 bitmap font and
+    /// `font::FALLBACK_GLYPH` for characters with no glyph (anything outside
+    /// printable ASCII). Clipped to the frame bounds.
+    pub fn draw_text(&mut self, x: isize, y: isize, text: &str) {
+        self.draw_text_with_fallback(x, y, text, font::FALLBACK_GLYPH);
+    }
+
+    /// Like `draw_text`, but draws `fallback` instead of
+    /// `font::FALLBACK_GLYPH` for characters with no glyph in the font.
+    pub fn draw_text_with_fallback(&mut self, x: isize, y: isize, text: &str, fallback: font::Glyph) {
+        for (i, ch) in text.chars().enumerate() {
+            let gx = x + (i * (GLYPH_WIDTH + GLYPH_SPACING)) as isize;
+            let glyph = font::glyph_for(ch).unwrap_or(fallback);
+
+            for col in 0..GLYPH_WIDTH {
+                for row in 0..GLYPH_HEIGHT {
+                    if glyph[col] & (1 << row) != 0 {
+                        let (px, py) = (gx + col as isize, y + row as isize);
+                        if px >= 0 && py >= 0 && (px as usize) < MONO_WIDTH && (py as usize) < MONO_HEIGHT {
+                            self.set_pixel(px as usize, py as usize, 255);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copies `src` onto this frame at `(x, y)`, clipped to the frame
+    /// bounds.
+    pub fn blit(&mut self, src: &MonoFrame, x: isize, y: isize) {
+        for sy in 0..MONO_HEIGHT {
+            for sx in 0..MONO_WIDTH {
+                let (dx, dy) = (x + sx as isize, y + sy as isize);
+                if dx >= 0 && dy >= 0 && (dx as usize) < MONO_WIDTH && (dy as usize) < MONO_HEIGHT {
+                    self.set_pixel(dx as usize, dy as usize, src.get_pixel(sx, sy));
+                }
+            }
+        }
+    }
+}
+
+impl Default for MonoFrame {
+    fn default() -> MonoFrame {
+        MonoFrame::new()
+    }
+}
+
+/// An owned 320x240 BGRA buffer matching the color lcd's background format.
+#[derive(Debug, Clone)]
+pub struct ColorFrame {
+    pixels: Box<[u8]>,
+}
+
+impl ColorFrame {
+    /// Creates a new frame, cleared to transparent black.
+    pub fn new() -> ColorFrame {
+        ColorFrame {
+            pixels: vec![0u8; COLOR_WIDTH * COLOR_HEIGHT * COLOR_BYTES_PER_PIXEL].into_boxed_slice(),
+        }
+    }
+
+    /// Returns the frame as a `320x240` BGRA byte slice, ready to hand to
+    /// `Lcd::set_color_background`, or use `Lcd::set_color_background_frame`
+    /// to pass the frame directly.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Sets every pixel to `color`.
+    pub fn clear(&mut self, color: Rgba) {
+        for y in 0..COLOR_HEIGHT {
+            for x in 0..COLOR_WIDTH {
+                self.set_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Sets a single pixel.
+    ///
+    /// ### Panics
+    /// Will panic if `x >= 320` or `y >= 240`.
+    ///
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Rgba) {
+        assert!(x < COLOR_WIDTH && y < COLOR_HEIGHT);
+        let i = (y * COLOR_WIDTH + x) * COLOR_BYTES_PER_PIXEL;
+        self.pixels[i] = color.b;
+        self.pixels[i + 1] = color.g;
+        self.pixels[i + 2] = color.r;
+        self.pixels[i + 3] = color.a;
+    }
+
+    /// Reads back a single pixel.
+    ///
+    /// ### Panics
+    /// Will panic if `x >= 320` or `y >= 240`.
+    ///
+    pub fn get_pixel(&self, x: usize, y: usize) -> Rgba {
+        assert!(x < COLOR_WIDTH && y < COLOR_HEIGHT);
+        let i = (y * COLOR_WIDTH + x) * COLOR_BYTES_PER_PIXEL;
+        Rgba::new(self.pixels[i + 2], self.pixels[i + 1], self.pixels[i], self.pixels[i + 3])
+    }
+
+    /// Fills the rectangle `(x, y)..(x + w, y + h)` with `color`, clipped to
+    /// the frame bounds.
+    pub fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize, color: Rgba) {
+        for py in y..y.saturating_add(h).min(COLOR_HEIGHT) {
+            for px in x..x.saturating_add(w).min(COLOR_WIDTH) {
+                self.set_pixel(px, py, color);
+            }
+        }
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)` using Bresenham's
+    /// algorithm, clipped to the frame bounds.
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, color: Rgba) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+
+        loop {
+            if x >= 0 && y >= 0 && (x as usize) < COLOR_WIDTH && (y as usize) < COLOR_HEIGHT {
+                self.set_pixel(x as usize, y as usize, color);
+            }
+
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Rasterizes `text` onto this frame with its top-left glyph corner at
+    /// `(x, y)` in `color`, using the crate's built-in 5x7 bitmap font and
+    /// `font::FALLBACK_GLYPH` for characters with no glyph (anything outside
+    /// printable ASCII). Clipped to the frame bounds.
+    pub fn draw_text(&mut self, x: isize, y: isize, text: &str, color: Rgba) {
+        self.draw_text_with_fallback(x, y, text, color, font::FALLBACK_GLYPH);
+    }
+
+    /// Like `draw_text`, but draws `fallback` instead of
+    /// `font::FALLBACK_GLYPH` for characters with no glyph in the font.
+    pub fn draw_text_with_fallback(&mut self, x: isize, y: isize, text: &str, color: Rgba, fallback: font::Glyph) {
+        for (i, ch) in text.chars().enumerate() {
+            let gx = x + (i * (GLYPH_WIDTH + GLYPH_SPACING)) as isize;
+            let glyph = font::glyph_for(ch).unwrap_or(fallback);
+
+            for col in 0..GLYPH_WIDTH {
+                for row in 0..GLYPH_HEIGHT {
+                    if glyph[col] & (1 << row) != 0 {
+                        let (px, py) = (gx + col as isize, y + row as isize);
+                        if px >= 0 && py >= 0 && (px as usize) < COLOR_WIDTH && (py as usize) < COLOR_HEIGHT {
+                            self.set_pixel(px as usize, py as usize, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copies `src` onto this frame at `(x, y)`, clipped to the frame
+    /// bounds.
+    pub fn blit(&mut self, src: &ColorFrame, x: isize, y: isize) {
+        for sy in 0..COLOR_HEIGHT {
+            for sx in 0..COLOR_WIDTH {
+                let (dx, dy) = (x + sx as isize, y + sy as isize);
+                if dx >= 0 && dy >= 0 && (dx as usize) < COLOR_WIDTH && (dy as usize) < COLOR_HEIGHT {
+                    self.set_pixel(dx as usize, dy as usize, src.get_pixel(sx, sy));
+                }
+            }
+        }
+    }
+}
+
+impl Default for ColorFrame {
+    fn default() -> ColorFrame {
+        ColorFrame::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mono_set_get_pixel_round_trips() {
+        let mut frame = MonoFrame::new();
+        assert_eq!(frame.get_pixel(3, 4), 0);
+        frame.set_pixel(3, 4, 200);
+        assert_eq!(frame.get_pixel(3, 4), 200);
+        assert_eq!(frame.get_pixel(0, 0), 0);
+    }
+
+    #[test]
+    fn mono_fill_rect_is_clipped_to_bounds() {
+        let mut frame = MonoFrame::new();
+        frame.fill_rect(MONO_WIDTH - 2, MONO_HEIGHT - 2, 5, 5, 255);
+
+        assert_eq!(frame.get_pixel(MONO_WIDTH - 2, MONO_HEIGHT - 2), 255);
+        assert_eq!(frame.get_pixel(MONO_WIDTH - 1, MONO_HEIGHT - 1), 255);
+        assert_eq!(frame.get_pixel(0, 0), 0);
+    }
+
+    #[test]
+    fn mono_draw_line_is_clipped_to_bounds() {
+        let mut frame = MonoFrame::new();
+        frame.draw_line(0, 0, (MONO_WIDTH + 10) as isize, 0, 255);
+
+        for x in 0..MONO_WIDTH {
+            assert_eq!(frame.get_pixel(x, 0), 255);
+        }
+    }
+
+    #[test]
+    fn mono_blit_copies_pixels_with_offset() {
+        let mut src = MonoFrame::new();
+        src.set_pixel(0, 0, 128);
+
+        let mut dst = MonoFrame::new();
+        dst.blit(&src, 2, 3);
+
+        assert_eq!(dst.get_pixel(2, 3), 128);
+        assert_eq!(dst.get_pixel(0, 0), 0);
+    }
+
+    #[test]
+    fn mono_draw_text_renders_the_known_glyph() {
+        // 'I' is [0x00, 0x41, 0x7f, 0x41, 0x00]: column 2 has every row lit,
+        // columns 1 and 3 have only the top and bottom row lit, and columns
+        // 0 and 4 are blank.
+        let mut frame = MonoFrame::new();
+        frame.draw_text(0, 0, "I");
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(2, row), 255);
+        }
+        assert_eq!(frame.get_pixel(1, 0), 255);
+        assert_eq!(frame.get_pixel(1, 3), 0);
+        assert_eq!(frame.get_pixel(0, 3), 0);
+        assert_eq!(frame.get_pixel(4, 3), 0);
+    }
+
+    #[test]
+    fn mono_draw_text_with_fallback_uses_the_given_glyph() {
+        let mut frame = MonoFrame::new();
+        frame.draw_text_with_fallback(0, 0, "\u{1}", [0x7f, 0x00, 0x00, 0x00, 0x00]);
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(0, row), 255);
+        }
+        assert_eq!(frame.get_pixel(1, 0), 0);
+    }
+
+    #[test]
+    fn mono_draw_text_is_clipped_at_the_frame_edge() {
+        let mut frame = MonoFrame::new();
+        // 'I's last two columns (0x41, 0x00) land past the frame edge and
+        // must be silently dropped rather than panicking; its middle column
+        // (0x7f, every row lit) lands on the last in-bounds pixel column.
+        frame.draw_text(MONO_WIDTH as isize - 3, 0, "I");
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(MONO_WIDTH - 1, row), 255);
+        }
+    }
+
+    #[test]
+    fn color_set_get_pixel_round_trips() {
+        let mut frame = ColorFrame::new();
+        let red = Rgba::new(255, 0, 0, 255);
+        frame.set_pixel(1, 1, red);
+        assert_eq!(frame.get_pixel(1, 1), red);
+        assert_eq!(frame.get_pixel(0, 0), Rgba::default());
+    }
+
+    #[test]
+    fn color_fill_rect_is_clipped_to_bounds() {
+        let mut frame = ColorFrame::new();
+        let blue = Rgba::new(0, 0, 255, 255);
+        frame.fill_rect(COLOR_WIDTH - 2, COLOR_HEIGHT - 2, 5, 5, blue);
+
+        assert_eq!(frame.get_pixel(COLOR_WIDTH - 1, COLOR_HEIGHT - 1), blue);
+        assert_eq!(frame.get_pixel(0, 0), Rgba::default());
+    }
+
+    #[test]
+    fn color_draw_line_is_clipped_to_bounds() {
+        let mut frame = ColorFrame::new();
+        let white = Rgba::new(255, 255, 255, 255);
+        frame.draw_line(0, 0, 0, (COLOR_HEIGHT + 10) as isize, white);
+
+        for y in 0..COLOR_HEIGHT {
+            assert_eq!(frame.get_pixel(0, y), white);
+        }
+    }
+
+    #[test]
+    fn color_blit_copies_pixels_with_offset() {
+        let mut src = ColorFrame::new();
+        let green = Rgba::new(0, 255, 0, 255);
+        src.set_pixel(0, 0, green);
+
+        let mut dst = ColorFrame::new();
+        dst.blit(&src, 4, 5);
+
+        assert_eq!(dst.get_pixel(4, 5), green);
+        assert_eq!(dst.get_pixel(0, 0), Rgba::default());
+    }
+
+    #[test]
+    fn color_draw_text_renders_the_known_glyph() {
+        let mut frame = ColorFrame::new();
+        let white = Rgba::new(255, 255, 255, 255);
+        frame.draw_text(0, 0, "I", white);
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(2, row), white);
+        }
+        assert_eq!(frame.get_pixel(1, 3), Rgba::default());
+    }
+
+    #[test]
+    fn color_draw_text_with_fallback_uses_the_given_glyph() {
+        let mut frame = ColorFrame::new();
+        let white = Rgba::new(255, 255, 255, 255);
+        frame.draw_text_with_fallback(0, 0, "\u{1}", white, [0x7f, 0x00, 0x00, 0x00, 0x00]);
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(0, row), white);
+        }
+        assert_eq!(frame.get_pixel(1, 0), Rgba::default());
+    }
+
+    #[test]
+    fn color_draw_text_is_clipped_at_the_frame_edge() {
+        let mut frame = ColorFrame::new();
+        let white = Rgba::new(255, 255, 255, 255);
+        frame.draw_text(COLOR_WIDTH as isize - 3, 0, "I", white);
+
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(frame.get_pixel(COLOR_WIDTH - 1, row), white);
+        }
+    }
+}
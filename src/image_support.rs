@@ -0,0 +1,133 @@
+//! Conversion from `image::DynamicImage` into the crate's background
+//! buffers. Gated behind the `image` feature.
+
+use image::{DynamicImage, FilterType, GenericImage};
+
+use super::{ColorFrame, MonoFrame, Rgba, COLOR_HEIGHT, COLOR_WIDTH, MONO_HEIGHT, MONO_WIDTH};
+
+/// Resizes `img` so that it fully covers `width`x`height`, then center-crops
+/// the overflow, similar to CSS `background-size: cover`.
+fn resize_to_fill(img: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+    let (w, h) = img.dimensions();
+    let scale = f64::max(width as f64 / w as f64, height as f64 / h as f64);
+    let rw = (w as f64 * scale).round().max(1.0) as u32;
+    let rh = (h as f64 * scale).round().max(1.0) as u32;
+
+    let mut resized = img.resize_exact(rw, rh, FilterType::Lanczos3);
+    let x = (rw - width) / 2;
+    let y = (rh - height) / 2;
+    resized.crop(x, y, width, height)
+}
+
+impl ColorFrame {
+    /// Builds a `ColorFrame` from `img`, resizing/cropping it to fill the
+    /// color lcd's 320x240 panel.
+    pub fn from_image(img: &DynamicImage) -> ColorFrame {
+        let fitted = resize_to_fill(img, COLOR_WIDTH as u32, COLOR_HEIGHT as u32);
+        let rgba = fitted.to_rgba();
+
+        let mut frame = ColorFrame::new();
+        for y in 0..COLOR_HEIGHT {
+            for x in 0..COLOR_WIDTH {
+                let p = rgba.get_pixel(x as u32, y as u32);
+                frame.set_pixel(x, y, Rgba::new(p.data[0], p.data[1], p.data[2], p.data[3]));
+            }
+        }
+        frame
+    }
+}
+
+/// Applies Floyd-Steinberg error-diffusion dithering to a `width`x`height`
+/// grayscale buffer, returning a same-sized buffer of `0`/`255` values. Kept
+/// free of `image` crate types so it can be exercised directly in tests.
+fn dither_floyd_steinberg(width: usize, height: usize, gray: &[u8]) -> Vec<u8> {
+    let mut errors: Vec<i32> = gray.iter().map(|&v| v as i32).collect();
+    let mut out = vec![0u8; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            let old = errors[y * width + x].max(0).min(255);
+            let new = if old >= 128 { 255 } else { 0 };
+            out[y * width + x] = new as u8;
+
+            let e = old - new;
+            let mut spread = |dx: isize, dy: isize, num: i32| {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if nx >= 0 && ny >= 0 && (nx as usize) < width && (ny as usize) < height {
+                    let i = ny as usize * width + nx as usize;
+                    errors[i] = (errors[i] + e * num / 16).max(0).min(255);
+                }
+            };
+            spread(1, 0, 7);
+            spread(-1, 1, 3);
+            spread(0, 1, 5);
+            spread(1, 1, 1);
+        }
+    }
+    out
+}
+
+impl MonoFrame {
+    /// Builds a `MonoFrame` from `img`, resizing it to fill the mono lcd's
+    /// 160x43 panel and applying Floyd-Steinberg error-diffusion dithering
+    /// so photos look good on a 1-bit panel rather than hard-thresholding.
+    pub fn from_image(img: &DynamicImage) -> MonoFrame {
+        let fitted = resize_to_fill(img, MONO_WIDTH as u32, MONO_HEIGHT as u32);
+        let gray = fitted.to_luma();
+
+        let mut values = vec![0u8; MONO_WIDTH * MONO_HEIGHT];
+        for y in 0..MONO_HEIGHT {
+            for x in 0..MONO_WIDTH {
+                values[y * MONO_WIDTH + x] = gray.get_pixel(x as u32, y as u32).data[0];
+            }
+        }
+
+        let dithered = dither_floyd_steinberg(MONO_WIDTH, MONO_HEIGHT, &values);
+        let mut frame = MonoFrame::new();
+        for y in 0..MONO_HEIGHT {
+            for x in 0..MONO_WIDTH {
+                frame.set_pixel(x, y, dithered[y * MONO_WIDTH + x]);
+            }
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dither_thresholds_flat_regions_without_neighbors() {
+        let gray = [40, 200];
+        let out = dither_floyd_steinberg(2, 1, &gray);
+        assert_eq!(out, vec![0, 255]);
+    }
+
+    #[test]
+    fn dither_diffuses_error_to_the_right_neighbor() {
+        // A single below-threshold pixel followed by a mid-gray one: the
+        // quantization error from the first pixel (old=100, new=0, e=100)
+        // should push 7/16 of it onto the second pixel (120 + 43 = 163),
+        // crossing the 128 threshold that 120 alone would not.
+        let gray = [100, 120];
+        let out = dither_floyd_steinberg(2, 1, &gray);
+        assert_eq!(out, vec![0, 255]);
+    }
+
+    #[test]
+    fn dither_clamps_accumulated_error_to_valid_range() {
+        let gray = [255, 255, 255];
+        let out = dither_floyd_steinberg(3, 1, &gray);
+        assert_eq!(out, vec![255, 255, 255]);
+
+        // 2x2: the bottom-left pixel first accumulates 250 + 39 = 289, which
+        // is clamped to 255 before the top-right pixel's -23 lands on it
+        // (232, error -23, so -10 reaches the bottom-right pixel: 133 -> 123,
+        // off). Without the clamp it would hold 266, carry no error, and the
+        // bottom-right pixel would stay at 133 and turn on.
+        let gray = [127, 73, 250, 165];
+        let out = dither_floyd_steinberg(2, 2, &gray);
+        assert_eq!(out, vec![0, 255, 255, 0]);
+    }
+}
@@ -1,5 +1,15 @@
 extern crate logi_lcd_sys as sys;
+#[cfg(feature = "image")]
+extern crate image;
 
+mod font;
+mod frame;
+#[cfg(feature = "image")]
+mod image_support;
+
+pub use frame::{ColorFrame, MonoFrame, Rgba};
+#[cfg(feature = "image")]
+pub use image::DynamicImage;
 pub use sys::LcdButton;
 pub use sys::BitFlags;
 pub use sys::MONO_WIDTH;
@@ -29,9 +39,112 @@ pub struct LcdTypeBoth;
 
 pub struct Lcd<T: LcdType> {
     type_flags: u32,
+    button_mask: BitFlags<LcdButton>,
+    button_state: ButtonState,
+    sent: SentCache,
     type_data: PhantomData<T>,
 }
 
+/// Caches the most recently sent background/text per region, so that
+/// `set_*` calls can skip the FFI round-trip when the content is unchanged.
+#[derive(Debug, Clone, Default)]
+struct SentCache {
+    mono_background: Option<Vec<u8>>,
+    mono_text: [Option<String>; 4],
+    color_background: Option<Vec<u8>>,
+    color_title: Option<(String, u8, u8, u8)>,
+    color_text: [Option<(String, u8, u8, u8)>; 4],
+}
+
+impl SentCache {
+    fn mono_background_matches(&self, bytemap: &[u8]) -> bool {
+        self.mono_background.as_ref().map_or(false, |last| last.as_slice() == bytemap)
+    }
+
+    fn mono_text_matches(&self, line_number: usize, text: &str) -> bool {
+        self.mono_text[line_number].as_ref().map_or(false, |last| last == text)
+    }
+
+    fn color_background_matches(&self, bitmap: &[u8]) -> bool {
+        self.color_background.as_ref().map_or(false, |last| last.as_slice() == bitmap)
+    }
+
+    fn color_title_matches(&self, wanted: &(String, u8, u8, u8)) -> bool {
+        self.color_title.as_ref().map_or(false, |last| last == wanted)
+    }
+
+    fn color_text_matches(&self, line_number: usize, wanted: &(String, u8, u8, u8)) -> bool {
+        self.color_text[line_number].as_ref().map_or(false, |last| last == wanted)
+    }
+}
+
+/// The direction of a button transition reported by `Lcd::poll_button_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonDirection {
+    /// The button was not pressed last update and is pressed now.
+    Pressed,
+    /// The button was pressed last update and is not pressed now.
+    Released,
+}
+
+/// A single button press/release edge, as produced by `Lcd::poll_button_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonEvent {
+    pub button: LcdButton,
+    pub direction: ButtonDirection,
+}
+
+/// Tracks button state across calls to `Lcd::update` so that edges can be
+/// detected instead of only instantaneous presses.
+#[derive(Debug, Clone, Copy)]
+struct ButtonState {
+    previous: BitFlags<LcdButton>,
+    current: BitFlags<LcdButton>,
+}
+
+impl ButtonState {
+    fn new() -> ButtonState {
+        ButtonState {
+            previous: BitFlags::empty(),
+            current: BitFlags::empty(),
+        }
+    }
+
+    /// Polls every button in `mask` and records it as the new current state.
+    fn update(&mut self, mask: BitFlags<LcdButton>) {
+        self.previous = self.current;
+        self.current = mask.iter().fold(BitFlags::empty(), |pressed, button| {
+            if unsafe { sys::LogiLcdIsButtonPressed(button.bits()) } {
+                pressed | button
+            } else {
+                pressed
+            }
+        });
+    }
+
+    /// Diffs the current state against the previous one, reporting an event
+    /// for every bit that was newly set or newly cleared.
+    fn events(&self) -> Vec<ButtonEvent> {
+        let mut events = Vec::new();
+
+        for button in (self.current & !self.previous).iter() {
+            events.push(ButtonEvent {
+                button: button,
+                direction: ButtonDirection::Pressed,
+            });
+        }
+
+        for button in (self.previous & !self.current).iter() {
+            events.push(ButtonEvent {
+                button: button,
+                direction: ButtonDirection::Released,
+            });
+        }
+
+        events
+    }
+}
+
 #[derive(Debug)]
 pub enum LcdError {
     NotConnected,
@@ -118,7 +231,10 @@ pub fn init_mono(app_name: &str) -> Result<Lcd<LcdTypeMono>, LcdError>  {
     let type_flags: u32 = LcdTypeFlags::MONO.into();
     unsafe {
         init(app_name, type_flags).map(|_| Lcd {
-            type_flags: type_flags, 
+            type_flags: type_flags,
+            button_mask: LcdButton::mono(),
+            button_state: ButtonState::new(),
+            sent: SentCache::default(),
             type_data: PhantomData,
         })
     }
@@ -136,7 +252,10 @@ pub fn init_color(app_name: &str) -> Result<Lcd<LcdTypeColor>, LcdError> {
     let type_flags: u32 = LcdTypeFlags::COLOR.into();
     unsafe {
         init(app_name, type_flags).map(|_| Lcd {
-            type_flags: type_flags, 
+            type_flags: type_flags,
+            button_mask: LcdButton::color(),
+            button_state: ButtonState::new(),
+            sent: SentCache::default(),
             type_data: PhantomData,
         })
     }
@@ -154,7 +273,10 @@ pub fn init_either(app_name: &str) -> Result<Lcd<LcdTypeBoth>, LcdError> {
     let type_flags = LcdTypeFlags::either().bits();
     unsafe {
         init(app_name, type_flags).map(|_| Lcd {
-            type_flags: type_flags, 
+            type_flags: type_flags,
+            button_mask: LcdButton::mono() | LcdButton::color(),
+            button_state: ButtonState::new(),
+            sent: SentCache::default(),
             type_data: PhantomData,
         })
     }
@@ -181,6 +303,35 @@ impl<T: LcdType> Lcd<T> {
         unsafe {
             sys::LogiLcdUpdate();
         }
+        self.button_state.update(self.button_mask);
+    }
+
+    /// Returns the button press/release edges detected by the most recent
+    /// call to `update`, relative to the `update` call before it.
+    ///
+    /// ### Return value:
+    /// A `Vec` containing one `ButtonEvent` per button that changed state.
+    /// Empty if no buttons changed state between those two `update` calls.
+    /// Calling this again before the next `update` returns the same events.
+    ///
+    /// ### Notes:
+    /// This spares applets from having to track the previous result of
+    /// `is_mono_buttons_pressed`/`is_color_buttons_pressed` themselves to
+    /// detect a button going down or up.
+    ///
+    pub fn poll_button_events(&mut self) -> Vec<ButtonEvent> {
+        self.button_state.events()
+    }
+
+    /// Invalidates the cache used to skip redundant `set_*` calls, forcing
+    /// the next call for every region to cross the FFI boundary again.
+    ///
+    /// ### Notes:
+    /// Useful after the device reconnects, since the SDK may have lost
+    /// whatever was last sent to it.
+    ///
+    pub fn force_next_update(&mut self) {
+        self.sent = SentCache::default();
     }
 }
 
@@ -213,14 +364,42 @@ impl<T: LcdType + LcdMono> Lcd<T> {
     ///
     pub fn set_mono_background(&mut self, bytemap: &[u8]) -> Result<(), LcdError> {
         assert_eq!(bytemap.len(), MONO_WIDTH * MONO_HEIGHT);
+
+        if self.sent.mono_background_matches(bytemap) {
+            return Ok(());
+        }
+
         unsafe {
             match sys::LogiLcdMonoSetBackground(bytemap.as_ptr()) {
-                true => Ok(()),
+                true => {
+                    self.sent.mono_background = Some(bytemap.to_vec());
+                    Ok(())
+                },
                 false => Err(LcdError::MonoBackground),
             }
         }
     }
 
+    /// Sets the specified image as background for the monochrome lcd device,
+    /// from an owned `MonoFrame` buffer.
+    ///
+    /// ### Notes:
+    /// Unlike `set_mono_background`, the size of `frame` is guaranteed
+    /// correct at construction time, so this can never panic on a
+    /// mismatched length.
+    ///
+    pub fn set_mono_background_frame(&mut self, frame: &MonoFrame) -> Result<(), LcdError> {
+        self.set_mono_background(frame.as_bytes())
+    }
+
+    /// Sets `img` as background for the monochrome lcd device, resizing it
+    /// to fill the 160x43 panel and dithering it to 1-bit with
+    /// Floyd-Steinberg error diffusion.
+    #[cfg(feature = "image")]
+    pub fn set_mono_background_image(&mut self, img: &DynamicImage) -> Result<(), LcdError> {
+        self.set_mono_background_frame(&MonoFrame::from_image(img))
+    }
+
     /// Sets the specified text in the requested line on the monochrome lcd device.
     ///
     /// ### Parameters:
@@ -234,9 +413,17 @@ impl<T: LcdType + LcdMono> Lcd<T> {
     pub fn set_mono_text(&mut self, line_number: usize, text: &str) -> Result<(), LcdError> {
         let ws = str_to_wchar(text)?;
         assert!(line_number < 4);
+
+        if self.sent.mono_text_matches(line_number, text) {
+            return Ok(());
+        }
+
         unsafe {
             match sys::LogiLcdMonoSetText(line_number as c_int, ws.as_ptr()) {
-                true => Ok(()),
+                true => {
+                    self.sent.mono_text[line_number] = Some(text.to_owned());
+                    Ok(())
+                },
                 false => Err(LcdError::MonoText),
             }
         }
@@ -260,24 +447,59 @@ impl<T: LcdType + LcdColor> Lcd<T> {
 
     pub fn set_color_background(&mut self, bitmap: &[u8]) -> Result<(), LcdError> {
         assert_eq!(bitmap.len(), COLOR_WIDTH * COLOR_HEIGHT * COLOR_BYTES_PER_PIXEL);
+
+        if self.sent.color_background_matches(bitmap) {
+            return Ok(());
+        }
+
         unsafe {
             match sys::LogiLcdColorSetBackground(bitmap.as_ptr()) {
-                true => Ok(()),
+                true => {
+                    self.sent.color_background = Some(bitmap.to_vec());
+                    Ok(())
+                },
                 false => Err(LcdError::ColorBackground),
             }
         }
     }
 
+    /// Sets the specified image as background for the color lcd device,
+    /// from an owned `ColorFrame` buffer.
+    ///
+    /// ### Notes:
+    /// Unlike `set_color_background`, the size of `frame` is guaranteed
+    /// correct at construction time, so this can never panic on a
+    /// mismatched length.
+    ///
+    pub fn set_color_background_frame(&mut self, frame: &ColorFrame) -> Result<(), LcdError> {
+        self.set_color_background(frame.as_bytes())
+    }
+
+    /// Sets `img` as background for the color lcd device, resizing/cropping
+    /// it to fill the 320x240 panel.
+    #[cfg(feature = "image")]
+    pub fn set_color_background_image(&mut self, img: &DynamicImage) -> Result<(), LcdError> {
+        self.set_color_background_frame(&ColorFrame::from_image(img))
+    }
+
     pub fn set_color_title(&mut self, text: &str, red: u8, green: u8, blue: u8)
         -> Result<(), LcdError>
     {
+        let wanted = (text.to_owned(), red, green, blue);
+        if self.sent.color_title_matches(&wanted) {
+            return Ok(());
+        }
+
         let ws = str_to_wchar(text)?;
 
         unsafe {
             match sys::LogiLcdColorSetTitle(ws.as_ptr(), red as c_int,
                 green as c_int, blue as c_int)
             {
-                true  => Ok(()),
+                true  => {
+                    self.sent.color_title = Some(wanted);
+                    Ok(())
+                },
                 false => Err(LcdError::ColorTitle),
             }
         }
@@ -288,11 +510,20 @@ impl<T: LcdType + LcdColor> Lcd<T> {
     {
         let ws = str_to_wchar(text)?;
         assert!(line_number < 4);
+
+        let wanted = (text.to_owned(), red, green, blue);
+        if self.sent.color_text_matches(line_number, &wanted) {
+            return Ok(());
+        }
+
         unsafe {
             match sys::LogiLcdColorSetText(line_number as c_int,
                 ws.as_ptr(), red as c_int, green as c_int, blue as c_int)
             {
-                true => Ok(()),
+                true => {
+                    self.sent.color_text[line_number] = Some(wanted);
+                    Ok(())
+                },
                 false => Err(LcdError::ColorText),
             }
         }
@@ -307,4 +538,75 @@ impl<T: LcdType> Drop for Lcd<T> {
         }
         INITIALIZED.store(false, Ordering::SeqCst);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sent_cache_matches_is_false_until_populated() {
+        let cache = SentCache::default();
+        assert!(!cache.mono_background_matches(&[0u8; 4]));
+        assert!(!cache.mono_text_matches(0, "hello"));
+        assert!(!cache.color_background_matches(&[0u8; 4]));
+        assert!(!cache.color_title_matches(&("hi".to_owned(), 1, 2, 3)));
+        assert!(!cache.color_text_matches(0, &("hi".to_owned(), 1, 2, 3)));
+    }
+
+    #[test]
+    fn sent_cache_matches_only_identical_content() {
+        let mut cache = SentCache::default();
+        cache.mono_background = Some(vec![1, 2, 3]);
+        cache.mono_text[2] = Some("score".to_owned());
+        cache.color_background = Some(vec![4, 5, 6]);
+        cache.color_title = Some(("title".to_owned(), 10, 20, 30));
+        cache.color_text[1] = Some(("lap 1".to_owned(), 1, 2, 3));
+
+        assert!(cache.mono_background_matches(&[1, 2, 3]));
+        assert!(!cache.mono_background_matches(&[1, 2, 4]));
+
+        assert!(cache.mono_text_matches(2, "score"));
+        assert!(!cache.mono_text_matches(2, "other"));
+        assert!(!cache.mono_text_matches(1, "score"));
+
+        assert!(cache.color_background_matches(&[4, 5, 6]));
+        assert!(!cache.color_background_matches(&[4, 5, 7]));
+
+        assert!(cache.color_title_matches(&("title".to_owned(), 10, 20, 30)));
+        assert!(!cache.color_title_matches(&("title".to_owned(), 10, 20, 31)));
+
+        assert!(cache.color_text_matches(1, &("lap 1".to_owned(), 1, 2, 3)));
+        assert!(!cache.color_text_matches(1, &("lap 2".to_owned(), 1, 2, 3)));
+        assert!(!cache.color_text_matches(0, &("lap 1".to_owned(), 1, 2, 3)));
+    }
+
+    #[test]
+    fn button_state_events_reports_pressed_and_released() {
+        let mono = LcdButton::mono();
+        let mut state = ButtonState {
+            previous: BitFlags::empty(),
+            current: mono,
+        };
+
+        let events = state.events();
+        assert_eq!(events.len(), mono.iter().count());
+        assert!(events.iter().all(|e| e.direction == ButtonDirection::Pressed));
+
+        state.previous = mono;
+        state.current = BitFlags::empty();
+        let events = state.events();
+        assert_eq!(events.len(), mono.iter().count());
+        assert!(events.iter().all(|e| e.direction == ButtonDirection::Released));
+    }
+
+    #[test]
+    fn button_state_events_is_empty_when_unchanged() {
+        let mono = LcdButton::mono();
+        let state = ButtonState {
+            previous: mono,
+            current: mono,
+        };
+        assert!(state.events().is_empty());
+    }
 }
\ No newline at end of file